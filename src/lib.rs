@@ -29,8 +29,10 @@
 //! 5) and 6) are guarded behind unsafe functions. Of course, the programmer is responsible to
 //!    maintain the invariant; but at last; it is requires using a visible unsafe block.
 //!
-//! The Operating System side is ignored. 7) and 8) could be addressed via mlock and mprotect
-//! syscalls. And as for 9), you should use an encrypted storage anyway.
+//! The Operating System side is ignored by [`SafeBox`] itself. 7) and 8) can be addressed with the
+//! `locked` feature, which adds [`LockedBox`], a parallel type hardened with `mlock` and
+//! `mprotect`; see its module docs for the tradeoffs. And as for 9), you should use an encrypted
+//! storage anyway.
 //!
 //!
 //! Example:
@@ -53,9 +55,19 @@
 //!
 //! See [`SafeBox::new_slice_with`] for a more elegant random initialization.
 
+use std::alloc::{self, Layout};
+use std::marker::PhantomData;
 use std::mem;
+use std::ptr::NonNull;
 use std::sync::atomic;
 
+use rand::Rng;
+
+#[cfg(all(feature = "locked", unix))]
+mod locked;
+#[cfg(all(feature = "locked", unix))]
+pub use locked::{LockedBox, LockedRef, LockedRefMut};
+
 /// Set the memory behind a value to zero.
 ///
 /// The value pointed by T will be replaced by zeroes in RAM. This is guaranteed to not be
@@ -79,9 +91,59 @@ pub unsafe fn memzero<T: ?Sized>(p: &mut T) {
     atomic::fence(atomic::Ordering::SeqCst);
 }
 
+/// Overwrite the memory behind a value with cryptographically random bytes drawn from `rng`.
+///
+/// A page of zeroes is a recognizable "hole" in a memory dump. Random bytes are indistinguishable
+/// from ordinary heap noise, and additionally defeat cold-boot / residual-charge analysis that can
+/// recover previously-zeroed regions.
+///
+/// This is unsafe, because T is left in some uninitialized state. It is easy to get into Undefined
+/// Behavior territory with this.
+pub unsafe fn memscramble<T: ?Sized, R: Rng>(p: &mut T, rng: &mut R) {
+    let len: usize = mem::size_of_val(p);
+
+    let raw: *mut u8 = (p as *mut T).cast();
+    for i in 0..len {
+        // write_volatile is guaranteed to not be elided nor reordered.
+        raw.add(i).write_volatile(rng.gen::<u8>());
+    }
+    atomic::fence(atomic::Ordering::SeqCst);
+}
+
+/// A strategy for wiping the backing memory of a [`SafeBox`] on drop.
+///
+/// See [`Zeroing`] and [`Scrambling`].
+pub trait WipeStrategy {
+    /// Wipe the memory behind `p`.
+    ///
+    /// This is unsafe for the same reason [`memzero`] is: `p` is left in some uninitialized state.
+    unsafe fn wipe<T: ?Sized>(p: &mut T);
+}
+
+/// Wipe with zeroes via [`memzero`]. The default [`WipeStrategy`] for [`SafeBox`].
+pub struct Zeroing;
+
+impl WipeStrategy for Zeroing {
+    unsafe fn wipe<T: ?Sized>(p: &mut T) {
+        memzero(p);
+    }
+}
+
+/// Wipe with cryptographically random bytes via [`memscramble`], drawing them from
+/// `rand::thread_rng`.
+pub struct Scrambling;
+
+impl WipeStrategy for Scrambling {
+    unsafe fn wipe<T: ?Sized>(p: &mut T) {
+        memscramble(p, &mut rand::thread_rng());
+    }
+}
+
 /// A safe box for your secrets.
 ///
-/// On Drop the content T is zeroed in RAM with [`memzero`].
+/// On Drop the content T is wiped in RAM according to the `W` [`WipeStrategy`], [`Zeroing`] by
+/// default. Use `SafeBox<T, Scrambling>` to overwrite with random bytes instead; see
+/// [`Scrambling`] for why you might want that.
 ///
 /// It can only be instantiated with Copy types. This forbids instantiating a `SafeBox<Vec<T>>` for
 /// example, which cannot be zeroed.
@@ -98,48 +160,131 @@ pub unsafe fn memzero<T: ?Sized>(p: &mut T) {
 /// implementation of Clone. It allocates a new SafeBox with a memcopy of the content.
 ///
 /// It is implemented as a wrapper around a Box<T>.
-pub struct SafeBox<T: ?Sized>(Box<T>);
+pub struct SafeBox<T: ?Sized, W: WipeStrategy = Zeroing>(PhantomData<W>, Box<T>);
+
+/// Error returned by the fallible `try_new*` constructors when the allocator cannot satisfy the
+/// request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryAllocError(Layout);
+
+impl std::fmt::Display for TryAllocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to allocate {} bytes (align {})",
+            self.0.size(),
+            self.0.align()
+        )
+    }
+}
+
+impl std::error::Error for TryAllocError {}
 
-impl<T: ?Sized> Drop for SafeBox<T> {
+/// Allocate `layout`, returning a dangling but well-aligned pointer for a zero-sized layout
+/// instead of calling into the allocator, which is UB for a zero-sized request.
+fn try_alloc_raw(layout: Layout) -> Result<NonNull<u8>, TryAllocError> {
+    if layout.size() == 0 {
+        return Ok(NonNull::dangling());
+    }
+    // SAFETY: layout has a non-zero size.
+    let ptr = unsafe { alloc::alloc(layout) };
+    NonNull::new(ptr).ok_or(TryAllocError(layout))
+}
+
+impl<T: ?Sized, W: WipeStrategy> Drop for SafeBox<T, W> {
     fn drop(&mut self) {
         unsafe {
-            memzero(&mut self.0 as &mut T);
+            W::wipe(&mut self.1 as &mut T);
         }
         // We only construct from T: Copy, which implies T: !Drop.
         // Therefor the content of the Box cannot have any destructor to run.
     }
 }
 
-impl<T: Copy> SafeBox<T> {
+impl<T: Copy, W: WipeStrategy> SafeBox<T, W> {
     /// Allocate a new SafeBox from the given value.
     ///
     /// Since v is passed by copy/move, it is advised to initialize with some safe value. Then use
     /// [`SafeBox::get_mut`] to write the secret value with the least amount of intermediate
     /// copies.
+    ///
+    /// Aborts on allocation failure. See [`SafeBox::try_new`] for a fallible version.
     pub fn new(v: T) -> Self {
-        Self(Box::new(v))
+        Self::try_new(v).unwrap_or_else(|e| alloc::handle_alloc_error(e.0))
+    }
+
+    /// Allocate a new SafeBox from the given value, returning [`TryAllocError`] instead of
+    /// aborting if the heap allocation fails.
+    ///
+    /// Secret buffers are sometimes large (key material, big tables), and in OOM-sensitive
+    /// contexts aborting on allocation failure is unacceptable. This bypasses `Box::new`, which
+    /// aborts internally, and allocates through [`alloc::alloc`] directly so the failure can be
+    /// reported.
+    pub fn try_new(v: T) -> Result<Self, TryAllocError> {
+        let layout = Layout::new::<T>();
+        let ptr = try_alloc_raw(layout)?.cast::<T>();
+        // SAFETY: ptr is freshly allocated for exactly one T, and not yet initialized.
+        unsafe {
+            ptr.as_ptr().write(v);
+            Ok(Self(PhantomData, Box::from_raw(ptr.as_ptr())))
+        }
+    }
+
+    /// Allocate an uninitialized `SafeBox`, then initialize it in place via `f`.
+    ///
+    /// Unlike [`SafeBox::new`], which takes the secret by value and can leave a stray copy on the
+    /// stack before it reaches the heap, `f` writes directly into the heap slot `new_with`
+    /// allocates for it. This mirrors the standard library's internal "write into raw
+    /// uninitialized target" approach, and is the right tool for key-derivation or RNG code that
+    /// can fill its destination in place.
+    pub fn new_with<F: FnOnce(&mut mem::MaybeUninit<T>)>(f: F) -> Self {
+        let mut uninit: SafeBox<mem::MaybeUninit<T>, W> = SafeBox::new(mem::MaybeUninit::uninit());
+        f(unsafe { uninit.get_mut() });
+        // SAFETY: MaybeUninit<T> is guaranteed to have the same memory layout as T, and f just
+        // initialized it.
+        unsafe { mem::transmute(uninit) }
     }
 }
 
-impl<T: Default + Copy> Default for SafeBox<T> {
+impl<T: Default + Copy, W: WipeStrategy> Default for SafeBox<T, W> {
     /// Allocate a new SafeBox with the default value.
     ///
     /// See [`SafeBox::new`].
     fn default() -> Self {
-        SafeBox::<T>::new(T::default())
+        SafeBox::<T, W>::new(T::default())
     }
 }
 
-impl<T: Copy> SafeBox<[T]> {
+impl<T: Copy, W: WipeStrategy> SafeBox<[T], W> {
     /// Allocate a new `SafeBox<[T]>`.
     ///
     /// The value `v` is copied into all `len` elements.
+    ///
+    /// Aborts on allocation failure. See [`SafeBox::try_new_slice`] for a fallible version.
     pub fn new_slice(v: T, len: usize) -> Self {
-        Self(vec![v; len].into_boxed_slice())
+        Self::try_new_slice(v, len).unwrap_or_else(|e| alloc::handle_alloc_error(e.0))
+    }
+
+    /// Allocate a new `SafeBox<[T]>`, returning [`TryAllocError`] instead of aborting if the heap
+    /// allocation fails.
+    ///
+    /// The value `v` is copied into all `len` elements. See [`SafeBox::try_new`] for the
+    /// rationale.
+    pub fn try_new_slice(v: T, len: usize) -> Result<Self, TryAllocError> {
+        let layout = Layout::array::<T>(len).expect("layout overflow");
+        let ptr = try_alloc_raw(layout)?.cast::<T>();
+        // SAFETY: ptr is freshly allocated for exactly len Ts, and not yet initialized.
+        unsafe {
+            for i in 0..len {
+                ptr.as_ptr().add(i).write(v);
+            }
+            let slice_ptr = std::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len);
+            Ok(Self(PhantomData, Box::from_raw(slice_ptr)))
+        }
     }
 }
 
-impl<T> SafeBox<[T]> {
+impl<T, W: WipeStrategy> SafeBox<[T], W> {
     /// Allocate a new `SafeBox<[T]>`.
     ///
     /// The function `f` is called to initialize the `len` elements.
@@ -147,26 +292,86 @@ impl<T> SafeBox<[T]> {
     /// ```
     /// use safebox::SafeBox;
     /// use rand::prelude::*;
-    /// let random_secret = SafeBox::new_slice_with(8, &random::<u8>);
+    /// let random_secret: SafeBox<[u8]> = SafeBox::new_slice_with(8, &random::<u8>);
     /// ```
+    ///
+    /// Aborts on allocation failure. See [`SafeBox::try_new_slice_with`] for a fallible version.
     pub fn new_slice_with<F: Fn() -> T>(len: usize, f: F) -> Self {
-        Self(
-            std::iter::repeat_with(f)
-                .take(len)
-                .collect::<Vec<T>>()
-                .into_boxed_slice(),
-        )
+        Self::try_new_slice_with(len, f).unwrap_or_else(|e| alloc::handle_alloc_error(e.0))
+    }
+
+    /// Allocate a new `SafeBox<[T]>`, returning [`TryAllocError`] instead of aborting if the heap
+    /// allocation fails.
+    ///
+    /// The function `f` is called to initialize the `len` elements. See [`SafeBox::try_new`] for
+    /// the rationale.
+    pub fn try_new_slice_with<F: Fn() -> T>(len: usize, f: F) -> Result<Self, TryAllocError> {
+        let layout = Layout::array::<T>(len).expect("layout overflow");
+        let ptr = try_alloc_raw(layout)?.cast::<T>();
+        // `f` is arbitrary caller code and may panic partway through the loop below. Guard the raw
+        // allocation until every element is written, so an unwind wipes what was initialized and
+        // frees the buffer instead of leaking it.
+        struct Guard<T, W: WipeStrategy> {
+            ptr: NonNull<T>,
+            cap: usize,
+            initialized: usize,
+            _wipe: PhantomData<W>,
+        }
+        impl<T, W: WipeStrategy> Drop for Guard<T, W> {
+            fn drop(&mut self) {
+                unsafe {
+                    let initialized =
+                        std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.initialized);
+                    W::wipe(initialized);
+                    let layout = Layout::array::<T>(self.cap).expect("layout overflow");
+                    alloc::dealloc(self.ptr.as_ptr().cast::<u8>(), layout);
+                }
+            }
+        }
+        let mut guard = Guard::<T, W> {
+            ptr,
+            cap: len,
+            initialized: 0,
+            _wipe: PhantomData,
+        };
+        unsafe {
+            for i in 0..len {
+                ptr.as_ptr().add(i).write(f());
+                guard.initialized = i + 1;
+            }
+        }
+        // Every element is initialized: disarm the guard and hand the buffer to `Box`.
+        mem::forget(guard);
+        // SAFETY: ptr is freshly allocated for exactly len Ts, all of which are now initialized.
+        unsafe {
+            let slice_ptr = std::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len);
+            Ok(Self(PhantomData, Box::from_raw(slice_ptr)))
+        }
+    }
+
+    /// Allocate an uninitialized `SafeBox<[T]>` of `len` elements, then initialize it in place via
+    /// `f`.
+    ///
+    /// See [`SafeBox::new_with`] for the rationale: `f` writes directly into the heap slice this
+    /// allocates, with no intermediate stack value.
+    pub fn new_slice_with_init<F: FnOnce(&mut [mem::MaybeUninit<T>])>(len: usize, f: F) -> Self {
+        let mut uninit: SafeBox<[mem::MaybeUninit<T>], W> =
+            SafeBox::new_slice_with(len, mem::MaybeUninit::uninit);
+        f(unsafe { uninit.get_mut() });
+        // SAFETY: MaybeUninit<T> is guaranteed to have the same memory layout as T, and f just
+        // initialized every element.
+        unsafe { mem::transmute(uninit) }
     }
 }
 
-impl<T: ?Sized> SafeBox<T> {
+impl<T: ?Sized, W: WipeStrategy> SafeBox<T, W> {
     /// A `&T` reference to the content.
     ///
     /// This is unsafe, because it allows for copying the content around in memory. Of course, a
     /// secret must be read at some point to be useful. But you bear all responsibility in copying
     /// it around.
     pub unsafe fn get_ref(&self) -> &T {
-        &self.0
+        &self.1
     }
 
     /// A `&mut T` reference to the content.
@@ -175,36 +380,282 @@ impl<T: ?Sized> SafeBox<T> {
     /// secret must be initialized at some point to be useful. But you bear all responsibility in
     /// copying it around.
     pub unsafe fn get_mut(&mut self) -> &mut T {
-        &mut self.0
+        &mut self.1
     }
 }
 
-impl<T: Copy> Clone for SafeBox<T> {
+impl<T: Copy, W: WipeStrategy> Clone for SafeBox<T, W> {
     /// Clone a `SafeBox<T>` via memcopy.
     fn clone(&self) -> Self {
-        // Box::new(*self.0) could copy on the stack. Hence the ptr dance.
-        let mut clone = SafeBox::new(mem::MaybeUninit::<T>::uninit());
-        let dest_ptr = clone.0.as_mut_ptr();
-        unsafe {
-            dest_ptr.copy_from_nonoverlapping(&*self.0 as *const T, 1);
-            // MaybeUninit is guaranteed to have the same memory layout as its content.
-            mem::transmute(clone)
-        }
+        // SafeBox::new(*self.1) could copy on the stack. new_with avoids that.
+        SafeBox::new_with(|dst: &mut mem::MaybeUninit<T>| unsafe {
+            dst.as_mut_ptr().copy_from_nonoverlapping(&*self.1 as *const T, 1);
+        })
     }
 }
 
-impl<T: Copy> Clone for SafeBox<[T]> {
+impl<T: Copy, W: WipeStrategy> Clone for SafeBox<[T], W> {
     /// Clone a `SafeBox<[T]>` via memcopy.
     fn clone(&self) -> Self {
-        let len = self.0.len();
-        let clone = SafeBox::new_slice(mem::MaybeUninit::<T>::uninit(), len);
+        let len = self.1.len();
+        SafeBox::new_slice_with_init(len, |dst| unsafe {
+            dst.as_mut_ptr()
+                .cast::<T>()
+                .copy_from_nonoverlapping(self.1.as_ptr(), len);
+        })
+    }
+}
+
+/// View `p` as its raw bytes.
+unsafe fn as_bytes<T: ?Sized>(p: &T) -> &[u8] {
+    std::slice::from_raw_parts(p as *const T as *const u8, mem::size_of_val(p))
+}
+
+/// View `p` as its raw bytes, mutably.
+unsafe fn as_bytes_mut<T: ?Sized>(p: &mut T) -> &mut [u8] {
+    std::slice::from_raw_parts_mut(p as *mut T as *mut u8, mem::size_of_val(p))
+}
+
+/// XOR `src` byte-for-byte into `dst`. Both must have the same length.
+fn xor_bytes_into(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+/// A secret split across two independently-allocated, individually-meaningless fragments.
+///
+/// Rather than holding the secret as one contiguous buffer, `SplitSafeBox` stores a random mask
+/// in one [`SafeBox`] and `secret XOR mask` in a second, unrelated allocation. A page dump or
+/// CPU-cache dump will typically only capture one of the two non-adjacent allocations, so neither
+/// fragment alone reveals the secret.
+///
+/// Because of this, `SplitSafeBox` cannot expose a plain `&T` into contiguous cleartext: the
+/// plaintext is reconstructed only transiently, by [`SplitSafeBox::get_ref`] and
+/// [`SplitSafeBox::get_mut`], into a scratch buffer that the caller owns and is responsible for
+/// wiping (e.g. with [`memzero`]) once done.
+///
+/// Both fragments are wiped on drop via [`SafeBox`]'s own `W` [`WipeStrategy`]: zeroed with the
+/// default [`Zeroing`], overwritten with random bytes with [`Scrambling`].
+pub struct SplitSafeBox<T: ?Sized, W: WipeStrategy = Zeroing> {
+    mask: SafeBox<T, W>,
+    masked: SafeBox<T, W>,
+}
+
+impl<T: Copy, W: WipeStrategy> SplitSafeBox<T, W> {
+    /// Split `secret` into a random mask and `secret XOR mask`, each in its own allocation.
+    pub fn new_split(mut secret: T) -> Self {
+        // Built with new_with so the mask and masked fragments are written directly into their
+        // heap slots, never through an intermediate stack copy.
+        let mask = SafeBox::<T, W>::new_with(|dst| unsafe {
+            let bytes =
+                std::slice::from_raw_parts_mut(dst.as_mut_ptr().cast::<u8>(), mem::size_of::<T>());
+            rand::thread_rng().fill(bytes);
+        });
+        let masked = SafeBox::<T, W>::new_with(|dst| unsafe {
+            let ptr = dst.as_mut_ptr();
+            ptr.copy_from_nonoverlapping(&secret as *const T, 1);
+            let bytes = std::slice::from_raw_parts_mut(ptr.cast::<u8>(), mem::size_of::<T>());
+            xor_bytes_into(bytes, as_bytes(mask.get_ref()));
+        });
+        // secret was read into masked above; it must not linger unwiped on the stack.
+        unsafe { memzero(&mut secret) };
+        Self { mask, masked }
+    }
+
+    /// Reconstruct the secret transiently into `scratch`, returning a reference into it.
+    ///
+    /// This is unsafe for the same reason [`SafeBox::get_ref`] is. Additionally, `scratch` now
+    /// holds contiguous cleartext, and the caller bears all responsibility for wiping it once
+    /// done.
+    pub unsafe fn get_ref<'a>(&self, scratch: &'a mut T) -> &'a T {
+        *scratch = *self.masked.get_ref();
+        xor_bytes_into(as_bytes_mut(scratch), as_bytes(self.mask.get_ref()));
+        scratch
+    }
+
+    /// Reconstruct the secret transiently into `scratch`, returning a mutable reference into it.
+    ///
+    /// See [`SplitSafeBox::get_ref`] for the safety caveats. Mutations through the returned
+    /// reference are not reflected back into the split storage.
+    pub unsafe fn get_mut<'a>(&mut self, scratch: &'a mut T) -> &'a mut T {
+        *scratch = *self.masked.get_ref();
+        xor_bytes_into(as_bytes_mut(scratch), as_bytes(self.mask.get_ref()));
+        scratch
+    }
+}
+
+impl<T: Copy, W: WipeStrategy> SplitSafeBox<[T], W> {
+    /// Split a `len`-element secret, filled with `v`, into a random mask and `secret XOR mask`,
+    /// each in its own allocation.
+    pub fn new_slice_split(v: T, len: usize) -> Self {
+        let mut mask = SafeBox::<[T], W>::new_slice(v, len);
+        rand::thread_rng().fill(unsafe { as_bytes_mut(mask.get_mut()) });
+        let mut masked = SafeBox::<[T], W>::new_slice(v, len);
         unsafe {
-            // MaybeUninit is guaranteed to have the same memory layout as its content.
-            let mut clone: SafeBox<[T]> = mem::transmute(clone);
+            xor_bytes_into(as_bytes_mut(masked.get_mut()), as_bytes(mask.get_ref()));
+        }
+        Self { mask, masked }
+    }
+
+    /// Reconstruct the secret transiently into `scratch`, returning a reference into it.
+    ///
+    /// See [`SplitSafeBox::get_ref`] for the safety caveats. `scratch` must have the same length
+    /// as the split secret.
+    pub unsafe fn get_ref<'a>(&self, scratch: &'a mut [T]) -> &'a [T] {
+        assert_eq!(scratch.len(), self.masked.get_ref().len());
+        scratch.copy_from_slice(self.masked.get_ref());
+        xor_bytes_into(as_bytes_mut(scratch), as_bytes(self.mask.get_ref()));
+        scratch
+    }
+
+    /// Reconstruct the secret transiently into `scratch`, returning a mutable reference into it.
+    ///
+    /// See [`SplitSafeBox::get_mut`] for the safety caveats. `scratch` must have the same length
+    /// as the split secret.
+    pub unsafe fn get_mut<'a>(&mut self, scratch: &'a mut [T]) -> &'a mut [T] {
+        assert_eq!(scratch.len(), self.masked.get_ref().len());
+        scratch.copy_from_slice(self.masked.get_ref());
+        xor_bytes_into(as_bytes_mut(scratch), as_bytes(self.mask.get_ref()));
+        scratch
+    }
+}
 
-            let dest_ptr = clone.0.as_mut_ptr();
-            dest_ptr.copy_from_nonoverlapping(self.0.as_ptr(), len);
-            clone
+/// A growable buffer for secrets whose length isn't known up front (incremental passphrase entry,
+/// streaming key agreement, etc.).
+///
+/// Building such a secret in a plain `Vec` is unsafe: `push`-triggered reallocation copies the
+/// data into a fresh buffer and leaves the old buffer un-wiped in the heap. Whenever `SafeVec`
+/// must grow, it instead allocates the new, larger buffer, copies the existing bytes over, then
+/// immediately wipes the entire old buffer with `W` before freeing it: copy the toxic waste, then
+/// wipe the old home. The live buffer is wiped on drop as well.
+///
+/// Like [`SafeBox`], access is guarded behind the `unsafe get_ref`/`get_mut` methods.
+pub struct SafeVec<T: Copy, W: WipeStrategy = Zeroing> {
+    ptr: NonNull<T>,
+    cap: usize,
+    len: usize,
+    _wipe: PhantomData<W>,
+}
+
+impl<T: Copy, W: WipeStrategy> SafeVec<T, W> {
+    /// Allocate a new, empty `SafeVec` with room for `cap` elements.
+    pub fn with_capacity(cap: usize) -> Self {
+        let layout = Layout::array::<T>(cap).expect("layout overflow");
+        let ptr = try_alloc_raw(layout)
+            .unwrap_or_else(|e| alloc::handle_alloc_error(e.0))
+            .cast::<T>();
+        Self {
+            ptr,
+            cap,
+            len: 0,
+            _wipe: PhantomData,
+        }
+    }
+
+    /// The number of elements currently held.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the `SafeVec` holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of elements that can be held before the next growth.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Grow the backing buffer so it can hold at least `min_cap` elements, wiping and freeing the
+    /// old one.
+    fn grow_to(&mut self, min_cap: usize) {
+        let new_cap = std::cmp::max(self.cap * 2, min_cap).max(1);
+        let layout = Layout::array::<T>(new_cap).expect("layout overflow");
+        let new_ptr = try_alloc_raw(layout)
+            .unwrap_or_else(|e| alloc::handle_alloc_error(e.0))
+            .cast::<T>();
+        unsafe {
+            new_ptr
+                .as_ptr()
+                .copy_from_nonoverlapping(self.ptr.as_ptr(), self.len);
+            if self.cap > 0 {
+                // Only `self.len` of `self.cap` elements are initialized: wipe the whole buffer as
+                // raw bytes rather than materializing a `&mut [T]` over the uninitialized tail.
+                let old_bytes = std::slice::from_raw_parts_mut(
+                    self.ptr.as_ptr().cast::<u8>(),
+                    self.cap * mem::size_of::<T>(),
+                );
+                W::wipe(old_bytes);
+                let old_layout = Layout::array::<T>(self.cap).expect("layout overflow");
+                alloc::dealloc(self.ptr.as_ptr().cast::<u8>(), old_layout);
+            }
+        }
+        self.ptr = new_ptr;
+        self.cap = new_cap;
+    }
+
+    /// Append `v`, growing the backing buffer first if it is full.
+    pub fn push(&mut self, v: T) {
+        if self.len == self.cap {
+            self.grow_to(self.len + 1);
+        }
+        unsafe {
+            self.ptr.as_ptr().add(self.len).write(v);
+        }
+        self.len += 1;
+    }
+
+    /// Append all of `values`, growing the backing buffer first if needed.
+    pub fn extend_from_slice(&mut self, values: &[T]) {
+        let needed = self.len + values.len();
+        if needed > self.cap {
+            self.grow_to(needed);
+        }
+        unsafe {
+            self.ptr
+                .as_ptr()
+                .add(self.len)
+                .copy_from_nonoverlapping(values.as_ptr(), values.len());
+        }
+        self.len += values.len();
+    }
+
+    /// A `&[T]` reference to the elements held so far.
+    ///
+    /// This is unsafe, because it allows for copying the content around in memory. Of course, a
+    /// secret must be read at some point to be useful. But you bear all responsibility in copying
+    /// it around.
+    pub unsafe fn get_ref(&self) -> &[T] {
+        std::slice::from_raw_parts(self.ptr.as_ptr(), self.len)
+    }
+
+    /// A `&mut [T]` reference to the elements held so far.
+    ///
+    /// This is unsafe, because it allows for copying the content around in memory. Of course, a
+    /// secret must be initialized at some point to be useful. But you bear all responsibility in
+    /// copying it around.
+    pub unsafe fn get_mut(&mut self) -> &mut [T] {
+        std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len)
+    }
+}
+
+impl<T: Copy, W: WipeStrategy> Drop for SafeVec<T, W> {
+    fn drop(&mut self) {
+        if self.cap == 0 {
+            return;
+        }
+        unsafe {
+            // Only `self.len` of `self.cap` elements are initialized: wipe the whole buffer as raw
+            // bytes rather than materializing a `&mut [T]` over the uninitialized tail.
+            let buf = std::slice::from_raw_parts_mut(
+                self.ptr.as_ptr().cast::<u8>(),
+                self.cap * mem::size_of::<T>(),
+            );
+            W::wipe(buf);
+            let layout = Layout::array::<T>(self.cap).expect("layout overflow");
+            alloc::dealloc(self.ptr.as_ptr().cast::<u8>(), layout);
         }
     }
 }
@@ -256,22 +707,86 @@ mod test {
     #[test]
     fn random_secret() {
         use rand::prelude::*;
-        let random_secret = SafeBox::new_slice_with(8, &random::<u8>);
+        let random_secret: SafeBox<[u8]> = SafeBox::new_slice_with(8, &random::<u8>);
         unsafe {
             println!("My secret: {:?}", random_secret.get_ref());
         }
     }
 
+    #[test]
+    fn split() {
+        let s: SplitSafeBox<u32> = SplitSafeBox::new_split(42_u32);
+        let mut scratch = 0_u32;
+        assert_eq!(unsafe { s.get_ref(&mut scratch) }, &42_u32);
+        unsafe { memzero(&mut scratch) };
+
+        let s: SplitSafeBox<[u32]> = SplitSafeBox::new_slice_split(42, 100);
+        let mut scratch = vec![0_u32; 100];
+        assert_eq!(unsafe { s.get_ref(&mut scratch) }, &vec![42_u32; 100][..]);
+        unsafe { memzero(scratch.as_mut_slice()) };
+    }
+
+    #[test]
+    fn vec() {
+        let mut v: SafeVec<u8> = SafeVec::with_capacity(2);
+        v.push(1);
+        v.push(2);
+        assert_eq!(unsafe { v.get_ref() }, &[1, 2]);
+        v.push(3); // triggers a grow past the initial capacity of 2.
+        v.extend_from_slice(&[4, 5, 6]);
+        assert_eq!(unsafe { v.get_ref() }, &[1, 2, 3, 4, 5, 6]);
+        unsafe { v.get_mut()[0] = 9 };
+        assert_eq!(unsafe { v.get_ref() }, &[9, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn new_with() {
+        let s: SafeBox<u32> = SafeBox::new_with(|dst| {
+            dst.write(42_u32);
+        });
+        assert_eq!(unsafe { s.get_ref() }, &42_u32);
+
+        let s: SafeBox<[u32]> = SafeBox::new_slice_with_init(4, |dst| {
+            for (i, slot) in dst.iter_mut().enumerate() {
+                slot.write(i as u32);
+            }
+        });
+        assert_eq!(unsafe { s.get_ref() }, &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn try_new() {
+        let s: SafeBox<u32> = SafeBox::try_new(42_u32).unwrap();
+        assert_eq!(unsafe { s.get_ref() }, &42_u32);
+
+        let s: SafeBox<[u32]> = SafeBox::try_new_slice(42, 100).unwrap();
+        assert_eq!(unsafe { s.get_ref() }, &vec![42_u32; 100][..]);
+
+        let s: SafeBox<[u32]> = SafeBox::try_new_slice_with(8, || 7_u32).unwrap();
+        assert_eq!(unsafe { s.get_ref() }, &[7_u32; 8][..]);
+    }
+
+    #[test]
+    fn scramble() {
+        let s: SafeBox<u32, Scrambling> = SafeBox::new(42_u32);
+        assert_eq!(unsafe { s.get_ref() }, &42_u32);
+        let p: *const u32 = unsafe { s.get_ref() };
+        drop(s);
+        // p is a dangling pointer now. Another test running concurrently might have reallocated
+        // the piece of RAM already. Let's play anyway.
+        assert_ne!(unsafe { p.read_volatile() }, 42_u32);
+    }
+
     #[test]
     fn clone() {
         use rand::prelude::*;
-        let a = SafeBox::new_slice_with(256, &random::<i32>);
+        let a: SafeBox<[i32]> = SafeBox::new_slice_with(256, &random::<i32>);
         let mut b = a.clone();
         unsafe {
             assert_eq!(a.get_ref(), b.get_ref());
         }
         drop(a);
-        let a = SafeBox::new_slice_with(256, &random::<i32>);
+        let a: SafeBox<[i32]> = SafeBox::new_slice_with(256, &random::<i32>);
         unsafe {
             assert_ne!(a.get_ref(), b.get_ref());
         }
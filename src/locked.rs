@@ -0,0 +1,192 @@
+//! OS-level hardening for secrets: `mlock` the backing pages so the OS cannot swap them to
+//! persistent storage, and `mprotect` them to `PROT_NONE` while idle so no other thread in the
+//! process can read or write them.
+//!
+//! This addresses threats 7 and 8 from the crate-level docs, which the plain, heap-only
+//! [`SafeBox`](crate::SafeBox) explicitly leaves unaddressed. Because per-page protection
+//! requires whole, page-aligned pages, [`LockedBox`] allocates its backing memory with `mmap`
+//! rather than the global allocator, which makes it considerably more expensive than `SafeBox`;
+//! that is why it is a parallel, opt-in type rather than `SafeBox`'s default behavior.
+//!
+//! Requires the `locked` feature. Unix only.
+
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+use crate::{WipeStrategy, Zeroing};
+
+/// Round `len_bytes` up to a whole number of pages, returning the mmap length.
+fn mmap_len(len_bytes: usize) -> usize {
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize };
+    len_bytes.max(1).div_ceil(page) * page
+}
+
+/// A page-aligned, `mlock`ed allocation for a single secret value.
+///
+/// The pages are `PROT_NONE` whenever no [`LockedBox::get_ref`] / [`LockedBox::get_mut`] guard is
+/// alive. Those guards flip the pages to `PROT_READ` / `PROT_READ | PROT_WRITE` for their
+/// lifetime, then restore `PROT_NONE` on drop. On [`LockedBox`] drop, access is restored, the
+/// content is wiped via `W`, and the pages are `munlock`ed and unmapped.
+pub struct LockedBox<T: Copy, W: WipeStrategy = Zeroing> {
+    ptr: NonNull<T>,
+    map_len: usize,
+    _wipe: PhantomData<W>,
+}
+
+impl<T: Copy, W: WipeStrategy> LockedBox<T, W> {
+    /// Allocate a new, page-aligned, `mlock`ed `LockedBox` from the given value.
+    ///
+    /// Aborts the process if `mmap`, `mlock` or the initial `mprotect` fail; a locked-memory
+    /// shortage is a deployment problem to fix (e.g. `RLIMIT_MEMLOCK`), not one callers can
+    /// meaningfully recover from, and a panic here could be caught by an unwinding caller and
+    /// leave the mapped, unprotected, un-mlocked page behind for the rest of the process.
+    pub fn new_locked(v: T) -> Self {
+        let map_len = mmap_len(mem::size_of::<T>());
+        unsafe {
+            let addr = libc::mmap(
+                std::ptr::null_mut(),
+                map_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if addr == libc::MAP_FAILED {
+                std::process::abort();
+            }
+            let Some(ptr) = NonNull::new(addr.cast::<T>()) else {
+                std::process::abort();
+            };
+            ptr.as_ptr().write(v);
+            if libc::mlock(addr, map_len) != 0 {
+                std::process::abort();
+            }
+            if libc::mprotect(addr, map_len, libc::PROT_NONE) != 0 {
+                std::process::abort();
+            }
+            Self {
+                ptr,
+                map_len,
+                _wipe: PhantomData,
+            }
+        }
+    }
+
+    /// A `&T` reference to the content, guarded by a [`LockedRef`] that restores `PROT_NONE` when
+    /// dropped.
+    ///
+    /// This is unsafe, because it allows for copying the content around in memory. Of course, a
+    /// secret must be read at some point to be useful. But you bear all responsibility in copying
+    /// it around.
+    pub unsafe fn get_ref(&self) -> LockedRef<'_, T> {
+        let addr = self.ptr.as_ptr().cast::<libc::c_void>();
+        if libc::mprotect(addr, self.map_len, libc::PROT_READ) != 0 {
+            std::process::abort();
+        }
+        LockedRef {
+            value: self.ptr.as_ref(),
+            addr,
+            map_len: self.map_len,
+        }
+    }
+
+    /// A `&mut T` reference to the content, guarded by a [`LockedRefMut`] that restores
+    /// `PROT_NONE` when dropped.
+    ///
+    /// This is unsafe, because it allows for copying the content around in memory. Of course, a
+    /// secret must be initialized at some point to be useful. But you bear all responsibility in
+    /// copying it around.
+    pub unsafe fn get_mut(&mut self) -> LockedRefMut<'_, T> {
+        let addr = self.ptr.as_ptr().cast::<libc::c_void>();
+        if libc::mprotect(addr, self.map_len, libc::PROT_READ | libc::PROT_WRITE) != 0 {
+            std::process::abort();
+        }
+        LockedRefMut {
+            value: self.ptr.as_mut(),
+            addr,
+            map_len: self.map_len,
+        }
+    }
+}
+
+impl<T: Copy, W: WipeStrategy> Drop for LockedBox<T, W> {
+    fn drop(&mut self) {
+        unsafe {
+            let addr = self.ptr.as_ptr().cast::<libc::c_void>();
+            // Restore write access before wiping; the pages may currently be PROT_NONE.
+            libc::mprotect(addr, self.map_len, libc::PROT_READ | libc::PROT_WRITE);
+            W::wipe(self.ptr.as_mut());
+            libc::munlock(addr, self.map_len);
+            libc::munmap(addr, self.map_len);
+        }
+    }
+}
+
+/// A `&T` into a [`LockedBox`], returned by [`LockedBox::get_ref`].
+///
+/// Restores the backing pages to `PROT_NONE` on drop.
+pub struct LockedRef<'a, T> {
+    value: &'a T,
+    addr: *mut libc::c_void,
+    map_len: usize,
+}
+
+impl<'a, T> Deref for LockedRef<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T> Drop for LockedRef<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            libc::mprotect(self.addr, self.map_len, libc::PROT_NONE);
+        }
+    }
+}
+
+/// A `&mut T` into a [`LockedBox`], returned by [`LockedBox::get_mut`].
+///
+/// Restores the backing pages to `PROT_NONE` on drop.
+pub struct LockedRefMut<'a, T> {
+    value: &'a mut T,
+    addr: *mut libc::c_void,
+    map_len: usize,
+}
+
+impl<'a, T> Deref for LockedRefMut<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T> DerefMut for LockedRefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<'a, T> Drop for LockedRefMut<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            libc::mprotect(self.addr, self.map_len, libc::PROT_NONE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scalar() {
+        let mut b: LockedBox<u32> = LockedBox::new_locked(42_u32);
+        assert_eq!(*unsafe { b.get_ref() }, 42_u32);
+        *unsafe { b.get_mut() } = 7_u32;
+        assert_eq!(*unsafe { b.get_ref() }, 7_u32);
+    }
+}